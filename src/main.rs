@@ -2,97 +2,304 @@ use colored::Colorize;
 use dirs::config_dir;
 use ini::Ini;
 use regex::Regex;
+use serde::Deserialize;
 use serde_json::from_str;
 use std::collections::{HashMap, VecDeque};
 use std::fs::{File, OpenOptions};
 use std::io::{self, BufRead, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
-    /// Add gnome terminal shortcuts to ghostty config in the config-file "gnome-shortcuts".
+    /// Add the source terminal's shortcuts to the ghostty config, in a source-specific
+    /// config-file (e.g. "gnome-shortcuts", "kitty-shortcuts").
     #[arg(short, long, default_value_t = false)]
     apply: bool,
-    
-    /// If shortcut conflicts with an existing keybinding, don't add to the new config.
-    #[arg(short = 'c', long, default_value_t = false)]
-    avoid_conflict: bool,
+
+    /// How to handle a converted shortcut that collides with an existing ghostty keybinding.
+    #[arg(long, value_enum, default_value = "keep-both")]
+    on_conflict: OnConflict,
+
+    /// Remove the config-file include and generated shortcuts file `ghosttify` added, rolling
+    /// back a previous `--apply`.
+    #[arg(long, default_value_t = false)]
+    undo: bool,
 
     /// Print found non-default ghostty keybindings.
     #[arg(long, default_value_t = false)]
     ghostty: bool,
 
-    /// Print gnome shortcuts.
+    /// Print the source terminal's shortcuts, converted to ghostty's format.
+    #[arg(long, default_value_t = false)]
+    converted: bool,
+
+    /// Show what `--apply` would write without touching any file.
+    #[arg(long, default_value_t = false)]
+    dry_run: bool,
+
+    /// Print the `--from` source's embedded `<source>_to_ghostty.json` key/action map and exit.
     #[arg(long, default_value_t = false)]
-    gnome: bool,
+    dump_map: bool,
+
+    /// Load a JSON file with the same `{"keys":{...},"actions":{...}}` shape and merge it over
+    /// the embedded defaults, with entries in this file taking precedence.
+    #[arg(long)]
+    map: Option<PathBuf>,
+
+    /// Which terminal's shortcuts to migrate from.
+    #[arg(long, value_enum, default_value = "gnome")]
+    from: Source,
+
+    /// Annotate printed ghostty shortcuts with the config file they were defined in, and which
+    /// earlier files' definitions of the same action they override.
+    #[arg(long, default_value_t = false)]
+    show_origin: bool,
+}
+
+/// The terminal emulator shortcuts are being migrated from.
+#[derive(ValueEnum, Clone, Debug)]
+enum Source {
+    Kitty,
+    Alacritty,
+    Gnome,
+}
+
+/// How to handle a converted shortcut that collides with an existing ghostty keybinding, modeled
+/// on `git config`'s `--replace-all`/`--unset` actions.
+#[derive(ValueEnum, Clone, Debug, PartialEq)]
+enum OnConflict {
+    /// Don't add the converted shortcut at all.
+    Skip,
+    /// Rewrite the colliding `keybind` line in place instead of appending a duplicate.
+    Replace,
+    /// Add the converted shortcut alongside the existing one (the default).
+    KeepBoth,
 }
 
-static MAP_STRING: &str = include_str!("./gnome_to_ghostty.json");
+/// A terminal emulator whose keybindings ghosttify knows how to read and migrate. Each source
+/// reads its own native config format into a common action -> shortcut map and names the
+/// bundled mapping file used to translate that map's keys and actions to ghostty's, so that
+/// `convert_shortcuts_to_ghostty` and `update_ghostty_config` stay source-agnostic.
+trait SourceTerminal {
+    /// Reads this terminal's shortcuts from its native config, keyed by action with the
+    /// terminal-native key combination as the value.
+    fn read_shortcuts(&self) -> HashMap<String, String>;
+
+    /// Name of the bundled `{"keys":{...},"actions":{...}}` mapping file used to translate this
+    /// source's shortcuts to ghostty's.
+    fn map_file_name(&self) -> &'static str;
+
+    /// Name of the ghostty config-file this source's converted shortcuts are written to and
+    /// included from, so migrating from different terminals doesn't clobber the same file.
+    fn shortcuts_file_name(&self) -> &'static str;
+
+    /// Whether this source's shortcut syntax uses `>` as a real leader/chord separator between
+    /// sequential key presses (kitty's `ctrl+a>c`, "press ctrl+a, then c") that must be preserved
+    /// verbatim in the ghostty output, rather than as bracket punctuation to strip like gnome's
+    /// `<Primary><Shift>T`. Defaults to `false` since most sources have no leader concept.
+    fn uses_leader_sequences(&self) -> bool {
+        false
+    }
+}
+
+struct Gnome;
+struct Kitty;
+struct Alacritty;
+
+impl SourceTerminal for Gnome {
+    fn read_shortcuts(&self) -> HashMap<String, String> {
+        get_gnome_shortcuts()
+    }
+
+    fn map_file_name(&self) -> &'static str {
+        "gnome_to_ghostty.json"
+    }
+
+    fn shortcuts_file_name(&self) -> &'static str {
+        "gnome-shortcuts"
+    }
+}
+
+impl SourceTerminal for Kitty {
+    fn read_shortcuts(&self) -> HashMap<String, String> {
+        get_kitty_shortcuts()
+    }
+
+    fn map_file_name(&self) -> &'static str {
+        "kitty_to_ghostty.json"
+    }
+
+    fn shortcuts_file_name(&self) -> &'static str {
+        "kitty-shortcuts"
+    }
+
+    fn uses_leader_sequences(&self) -> bool {
+        true
+    }
+}
+
+impl SourceTerminal for Alacritty {
+    fn read_shortcuts(&self) -> HashMap<String, String> {
+        get_alacritty_shortcuts()
+    }
+
+    fn map_file_name(&self) -> &'static str {
+        "alacritty_to_ghostty.json"
+    }
+
+    fn shortcuts_file_name(&self) -> &'static str {
+        "alacritty-shortcuts"
+    }
+}
+
+/// Picks the `SourceTerminal` implementation for the `--from` flag.
+fn source_terminal(source: &Source) -> Box<dyn SourceTerminal> {
+    match source {
+        Source::Gnome => Box::new(Gnome),
+        Source::Kitty => Box::new(Kitty),
+        Source::Alacritty => Box::new(Alacritty),
+    }
+}
+
+static GNOME_MAP_STRING: &str = include_str!("./maps/gnome_to_ghostty.json");
+static KITTY_MAP_STRING: &str = include_str!("./maps/kitty_to_ghostty.json");
+static ALACRITTY_MAP_STRING: &str = include_str!("./maps/alacritty_to_ghostty.json");
+
+/// Resolves a `SourceTerminal::map_file_name` to its embedded JSON contents. `include_str!`
+/// needs a compile-time literal path, so the bundled maps are matched here rather than loaded
+/// dynamically from `map_file_name`.
+fn embedded_map_string(map_file_name: &str) -> &'static str {
+    match map_file_name {
+        "gnome_to_ghostty.json" => GNOME_MAP_STRING,
+        "kitty_to_ghostty.json" => KITTY_MAP_STRING,
+        "alacritty_to_ghostty.json" => ALACRITTY_MAP_STRING,
+        other => panic!("No embedded mapping file named {other}"),
+    }
+}
+
+/// Loads the key/action mapping used to translate a source terminal's shortcuts to ghostty
+/// ones. Starts from the embedded defaults for `map_file_name` and, if `user_map_path` is given,
+/// deep-merges a user-supplied JSON file of the same `{"keys":{...},"actions":{...}}` shape over
+/// it. Entries in the user's file win, and an empty-string value still means "drop this binding".
+///
+/// Args:
+/// - map_file_name: the bundled mapping file to use as the defaults, from
+///     `SourceTerminal::map_file_name`.
+/// - user_map_path: optional path to a user-supplied mapping file to merge over the defaults.
+fn load_map(
+    map_file_name: &str,
+    user_map_path: Option<&Path>,
+) -> HashMap<String, HashMap<String, String>> {
+    let mut mapping: HashMap<String, HashMap<String, String>> =
+        from_str(embedded_map_string(map_file_name)).unwrap();
+
+    if let Some(path) = user_map_path {
+        let user_map_string =
+            std::fs::read_to_string(path).expect("Error reading the file passed to --map");
+        let user_map: HashMap<String, HashMap<String, String>> =
+            from_str(&user_map_string).expect("Error parsing the file passed to --map as JSON");
+
+        for (section, entries) in user_map {
+            mapping.entry(section).or_default().extend(entries);
+        }
+    }
+
+    mapping
+}
 
-/// Convert a gnome shortcut to ghostty using the `gnome_to_ghostty.json` file which provides
-/// mappings for the repsentation of keys used in gnome configurations to ghostty's. gnome
-/// shortcuts surround special keys with angle brackets and don't use any delimiting character
-/// between keys in a shortcut. When converting,
-///     - if no ghostty key is found for the gnome key in the mapping, use the same gnome key,
-///     - if the gnome shortcut is `disabled`, ignore the gnome shortcut,
-///     - if the ghostty key is an empty string, ignore the gnome shortcut (not a supported key).
+/// Convert a source terminal's shortcut to ghostty using the mapping's `keys` section, which
+/// provides the mapping for the representation of keys used in the source's configuration to
+/// ghostty's. Source shortcuts may surround special keys with angle brackets and not use any
+/// delimiting character between keys in a shortcut (as gnome does). When converting,
+///     - if no ghostty key is found for the source key in the mapping, use the same source key,
+///     - if the source shortcut is `disabled`, ignore the shortcut,
+///     - if the ghostty key is an empty string, ignore the shortcut (not a supported key).
 /// https://github.com/ghostty-org/ghostty/blob/d6e76858164d52cff460fedc61ddf2e560912d71/src/input/key.zig#L255
 ///
+/// `>` is treated as the boundary between a shortcut's individual chords. For most sources it's
+/// just bracket punctuation alongside `<` (gnome's `<Primary><Shift>T`), so every chord collapses
+/// back into a single simultaneous combo joined with `+`. When `preserve_leader` is set (kitty's
+/// `ctrl+a>c` two-step leader sequences), the chords are instead rejoined with `>` so the
+/// sequential structure survives the conversion instead of being flattened into `ctrl+a+c`.
+///
 /// Args:
-/// - gnome_shortcut: The gnome shorcut being converted.
-/// - gnome_to_ghostty_shortcut: A hashmap with a mapping from gnome configuration key
+/// - source_shortcut: The source terminal's shorcut being converted.
+/// - source_to_ghostty_shortcut: A hashmap with a mapping from the source terminal's key
 ///     representatioin to ghostty's.
-fn convert_gnome_shortcut_to_ghostty(
-    gnome_shortcut: &String,
-    gnome_to_ghostty_shortcut: &HashMap<String, String>,
+/// - preserve_leader: whether `>` separates real sequential chords that must stay `>`-joined in
+///     the output, from `SourceTerminal::uses_leader_sequences`.
+fn convert_shortcut_to_ghostty(
+    source_shortcut: &String,
+    source_to_ghostty_shortcut: &HashMap<String, String>,
+    preserve_leader: bool,
 ) -> Option<String> {
-    let ghostty_shortcut = gnome_shortcut
-        .split(&['>', '<'][..])
-        .filter(|key| !key.is_empty())
-        .filter_map(|key| {
-            let mapped = gnome_to_ghostty_shortcut.get(key);
-
-            match mapped {
-                Some(ghostty_key) => {
-                    if ghostty_key.is_empty() || ghostty_key == "disabled" {
-                        return None;
-                    } else {
-                        return Some(ghostty_key.to_string());
+    if source_shortcut == "disabled" {
+        return None;
+    }
+
+    let chords: Vec<String> = source_shortcut
+        .split('>')
+        .map(|chord| {
+            chord
+                .split(&['<', '+'][..])
+                .filter(|key| !key.is_empty())
+                .map(|key| {
+                    let mapped = source_to_ghostty_shortcut.get(key);
+
+                    match mapped {
+                        Some(ghostty_key) => {
+                            if ghostty_key.is_empty() || ghostty_key == "disabled" {
+                                return None;
+                            } else {
+                                return Some(ghostty_key.to_string());
+                            }
+                        }
+                        None => Some(key.to_string()),
                     }
-                }
-                None => Some(key.to_string()),
-            }
+                })
+                .collect::<Option<Vec<_>>>()
+                .map(|keys| keys.join("+"))
         })
-        .fold(String::new(), |acc, s| acc + "+" + &s);
+        .collect::<Option<Vec<_>>>()?
+        .into_iter()
+        .filter(|chord| !chord.is_empty())
+        .collect();
 
-    if ghostty_shortcut.is_empty() {
+    if chords.is_empty() {
         return None;
     }
-    Some(ghostty_shortcut[1..].to_string())
+
+    let separator = if preserve_leader { ">" } else { "+" };
+    Some(chords.join(separator))
 }
 
-/// Converts all the gnome shortcuts to ghostty shortcuts using the
-/// `convert_gnome_shortcut_to_ghostty` function. If the shortcut can not be converted or the
-/// action has no parallel in ghostty, the shortcut is ignore. The conversions for both the keys
-/// and the actions are in the gnome_to_ghostty.json` file.
+/// Converts all of a source terminal's shortcuts to ghostty shortcuts using the
+/// `convert_shortcut_to_ghostty` function. If the shortcut can not be converted or the
+/// action has no parallel in ghostty, the shortcut is ignored. The conversions for both the keys
+/// and the actions come from the source's mapping file, as loaded by `load_map`.
 ///
 /// Args:
-/// - gnome_shortcuts: A hashmap of the gnome shorcuts with the action as the key and shortcut as
-///     the value.
-fn convert_gnome_to_ghostty_shortcuts(
-    gnome_shortcuts: HashMap<String, String>,
+/// - source_shortcuts: A hashmap of the source terminal's shorcuts with the action as the key
+///     and shortcut as the value.
+/// - source_to_ghostty: the merged `{"keys":{...},"actions":{...}}` mapping to convert with, as
+///     produced by `load_map`.
+/// - preserve_leader: whether `>` in a shortcut separates real sequential chords, passed through
+///     to `convert_shortcut_to_ghostty`, from `SourceTerminal::uses_leader_sequences`.
+fn convert_shortcuts_to_ghostty(
+    source_shortcuts: HashMap<String, String>,
+    source_to_ghostty: &HashMap<String, HashMap<String, String>>,
+    preserve_leader: bool,
 ) -> HashMap<String, String> {
-    let gnome_to_ghostty: HashMap<String, HashMap<String, String>> = from_str(MAP_STRING).unwrap();
-    let gnome_to_ghostty_shortcut = gnome_to_ghostty.get("keys").unwrap();
-    let gnome_to_ghostty_action = gnome_to_ghostty.get("actions").unwrap();
+    let source_to_ghostty_shortcut = source_to_ghostty.get("keys").unwrap();
+    let source_to_ghostty_action = source_to_ghostty.get("actions").unwrap();
 
-    gnome_shortcuts
+    source_shortcuts
         .iter()
         .flat_map(|(action, binding)| {
-            let ghostty_action = gnome_to_ghostty_action.get(action);
+            let ghostty_action = source_to_ghostty_action.get(action);
             match ghostty_action {
                 Some(com) => {
                     if com.is_empty() {
@@ -103,7 +310,7 @@ fn convert_gnome_to_ghostty_shortcuts(
             }
 
             let ghostty_shortcut = if let Some(shortcut) =
-                convert_gnome_shortcut_to_ghostty(binding, &gnome_to_ghostty_shortcut)
+                convert_shortcut_to_ghostty(binding, &source_to_ghostty_shortcut, preserve_leader)
             {
                 shortcut
             } else {
@@ -134,6 +341,87 @@ fn get_gnome_shortcuts() -> HashMap<String, String> {
         .collect::<HashMap<String, String>>()
 }
 
+/// Parses `kitty.conf` lines of the form `map <keys> <action>` into a map from action to key
+/// combination. Split out from `get_kitty_shortcuts` so the regex-based parsing can be exercised
+/// without a real `kitty.conf` on disk.
+fn parse_kitty_shortcuts<I: IntoIterator<Item = String>>(lines: I) -> HashMap<String, String> {
+    let map_re = Regex::new(r#"^\s*map\s+(\S+)\s+(.+?)\s*$"#).unwrap();
+
+    let mut shortcuts = HashMap::new();
+    for line in lines {
+        if let Some(cap) = map_re.captures(&line) {
+            shortcuts.insert(cap[2].to_string(), cap[1].to_string());
+        }
+    }
+
+    shortcuts
+}
+
+/// Get the kitty shortcuts from `kitty.conf`, which holds bindings as `map <keys> <action>`
+/// lines, and produce a map with the action as the key and the key combination as the value.
+fn get_kitty_shortcuts() -> HashMap<String, String> {
+    let kitty_config_dir = config_dir().unwrap().join("kitty");
+
+    let Ok(lines) = read_lines(kitty_config_dir.join("kitty.conf")) else {
+        return HashMap::new();
+    };
+    parse_kitty_shortcuts(lines.map_while(Result::ok))
+}
+
+#[derive(Deserialize)]
+struct AlacrittyConfig {
+    keyboard: AlacrittyKeyboard,
+}
+
+#[derive(Deserialize)]
+struct AlacrittyKeyboard {
+    #[serde(default)]
+    bindings: Vec<AlacrittyBinding>,
+}
+
+#[derive(Deserialize)]
+struct AlacrittyBinding {
+    key: String,
+    #[serde(default)]
+    mods: Option<String>,
+    action: String,
+}
+
+/// Parses an `alacritty.toml` string's `[[keyboard.bindings]]` entries into a map from action to
+/// `mods+key` combination. Alacritty joins multiple modifiers in `mods` with `|` (e.g.
+/// `"Control|Shift"`), so that's rewritten to `+` before being joined with the key, giving
+/// `convert_shortcut_to_ghostty` the same `+`-delimited shape it expects from every other source.
+/// Split out from `get_alacritty_shortcuts` so the TOML parsing can be exercised without a real
+/// `alacritty.toml` on disk.
+fn parse_alacritty_shortcuts(conf: &str) -> HashMap<String, String> {
+    let Ok(config) = toml::from_str::<AlacrittyConfig>(conf) else {
+        return HashMap::new();
+    };
+
+    config
+        .keyboard
+        .bindings
+        .into_iter()
+        .map(|binding| {
+            let combo = match binding.mods {
+                Some(mods) => format!("{}+{}", mods.replace('|', "+"), binding.key),
+                None => binding.key,
+            };
+            (binding.action, combo)
+        })
+        .collect()
+}
+
+/// Get the alacritty shortcuts from `alacritty.toml`'s `[[keyboard.bindings]]` entries and
+/// produce a map with the action as the key and the `mods+key` combination as the value.
+fn get_alacritty_shortcuts() -> HashMap<String, String> {
+    let alacritty_config_dir = config_dir().unwrap().join("alacritty");
+    let Ok(conf) = std::fs::read_to_string(alacritty_config_dir.join("alacritty.toml")) else {
+        return HashMap::new();
+    };
+    parse_alacritty_shortcuts(&conf)
+}
+
 /// Gets all the ghostty config files including the main `config` file. The rest are
 /// files provided through the "config-file" option. Using BFS to preserve order logic enforced
 /// by ghostty configuration.
@@ -164,30 +452,81 @@ fn get_config_files() -> Vec<String> {
     config_file_paths
 }
 
-/// Get all the shortcuts defined in the ghostty config. This includes keybindings in config files
-/// provided by the "config-file" option. https://ghostty.org/docs/config/reference#config-file
-/// Also, bindings defined later shadow earlier ones if in the same file.
-fn get_ghostty_shortcuts() -> HashMap<String, String> {
+/// Parses a single ghostty config line of the form `keybind = <trigger>=<action>`.
+///
+/// A greedy `(.*)=(.*)` regex splits on the *last* `=`, which mis-splits actions whose
+/// arguments themselves contain `=` (`text:foo=bar`). Instead, only the *first* `=` after `keybind =` is
+/// treated as the trigger/action separator; everything before it is the trigger (prefix and
+/// `>`-chained chords included verbatim) and everything after is kept whole as the action. The
+/// trigger's prefix is preserved rather than stripped so that `global:ctrl+q` and `ctrl+q` are
+/// tracked as distinct bindings.
+///
+/// Args:
+/// - line: a single line from a ghostty config file.
+fn parse_keybind_line(line: &str) -> Option<(String, String)> {
+    let rest = line.trim().strip_prefix("keybind")?.trim_start();
+    let rest = rest.strip_prefix('=')?.trim_start();
+    let (trigger, action) = rest.split_once('=')?;
+    Some((trigger.trim().to_string(), action.trim().to_string()))
+}
+
+/// Every definition of a ghostty keybind found for a single action, across the BFS-discovered
+/// config files, in the order those files were visited. The last entry is the one actually in
+/// effect, since later `config-file` includes shadow earlier ones for the same action.
+struct GhosttyBindingHistory {
+    entries: Vec<(String, String)>, // (origin_file, trigger)
+}
+
+impl GhosttyBindingHistory {
+    fn trigger(&self) -> &str {
+        &self.entries.last().expect("history always has an entry").1
+    }
+
+    fn origin_file(&self) -> &str {
+        &self.entries.last().expect("history always has an entry").0
+    }
+}
+
+/// Get all the shortcuts defined in the ghostty config, keeping track of which BFS-discovered
+/// config file (the main `config`, or a `config-file=` include) defined each one, and every file
+/// that redefined the same action. This includes keybindings in config files provided by the
+/// "config-file" option. https://ghostty.org/docs/config/reference#config-file Also, bindings
+/// defined later shadow earlier ones.
+fn get_ghostty_shortcuts_with_origin() -> HashMap<String, GhosttyBindingHistory> {
     let ghostty_config_dir = config_dir().unwrap().join("ghostty");
-    let keybinding_re = Regex::new(r#"keybind\s*=\s*(.*)=\s*(.*)\s*"#).unwrap();
 
-    let mut shortcuts: HashMap<String, String> = HashMap::new();
+    let mut origins: HashMap<String, GhosttyBindingHistory> = HashMap::new();
     let config_file_paths = get_config_files();
 
     for config_file_path in config_file_paths {
-        let config_file = ghostty_config_dir.join(config_file_path);
+        let config_file = ghostty_config_dir.join(&config_file_path);
 
         if let Ok(lines) = read_lines(config_file) {
             for line in lines.map_while(Result::ok) {
-                if let Some(cap) = keybinding_re.captures(&line) {
-                    shortcuts.insert(cap[2].to_string(), cap[1].to_string());
+                if let Some((trigger, action)) = parse_keybind_line(&line) {
+                    origins
+                        .entry(action)
+                        .or_insert_with(|| GhosttyBindingHistory { entries: Vec::new() })
+                        .entries
+                        .push((config_file_path.clone(), trigger));
                 }
             }
         }
     }
 
-    shortcuts
+    origins
 }
+
+/// Flattens the per-action binding history down to the single trigger currently in effect for
+/// each action, matching the shape every other part of the pipeline (conflict detection,
+/// `--apply`) expects.
+fn flatten_ghostty_shortcuts(origins: &HashMap<String, GhosttyBindingHistory>) -> HashMap<String, String> {
+    origins
+        .iter()
+        .map(|(action, history)| (action.clone(), history.trigger().to_string()))
+        .collect()
+}
+
 /// Print the ghostty shortcuts.
 ///
 /// Args:
@@ -204,22 +543,138 @@ fn print_ghostty_shortcuts(shortcuts: &HashMap<String, String>) {
     println!();
 }
 
-/// Updates the ghostty config with converted gnome shortcuts. New bindings are added if
-/// the action does not only have the same binding already.
+/// Print the ghostty shortcuts annotated with the config file each one came from and, if an
+/// earlier file's definition of the same action was shadowed, which files it came from. Enabled
+/// by `--show-origin`, modeled on `git config --show-origin`/`--show-scope`.
+///
+/// Args:
+/// - origins: map from the action to its full binding history, as produced by
+///     `get_ghostty_shortcuts_with_origin`.
+fn print_ghostty_shortcuts_with_origin(origins: &HashMap<String, GhosttyBindingHistory>) {
+    println!(
+        "\t    {}\t{}\t{}",
+        "Binding".to_string().cyan(),
+        "Action".to_string().magenta(),
+        "Origin".to_string().yellow()
+    );
+    for (action, history) in origins {
+        print!(
+            "keybind = {}={}\t{}",
+            history.trigger().bright_cyan(),
+            action.magenta(),
+            history.origin_file().yellow()
+        );
+
+        if history.entries.len() > 1 {
+            let shadowed = history.entries[..history.entries.len() - 1]
+                .iter()
+                .map(|(file, _)| file.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            print!(" {}", format!("(overrides {shadowed})").dimmed());
+        }
+        println!();
+    }
+    println!();
+}
+
+/// Decides which converted gnome shortcuts should be considered for writing to the ghostty
+/// config, without touching any file. Shared by the real `--apply` path and the `--dry-run`
+/// preview so the two can never drift apart. `--on-conflict skip` filters out collisions here;
+/// `replace`/`keep-both` both keep every converted shortcut, since they differ only in how
+/// `update_ghostty_config` writes a colliding entry.
 ///
 /// Args:
 /// - converted_gnome_shortcuts: map from the action to the keybinding of the converted gnome
 ///     shortcuts
 /// - ghostty_shortcuts: map from the action to the keybinding for the ghostty config shortcuts.
 ///    This accounts for different config files and the order in which bindings are stated.
-/// - avoid_conflict: only apply shortcuts that do not conflict with existing key bindings
+/// - on_conflict: how to handle a converted shortcut that collides with an existing one
+fn plan_ghostty_updates(
+    converted_gnome_shortcuts: &HashMap<String, String>,
+    ghostty_shortcuts: &HashMap<String, String>,
+    on_conflict: &OnConflict,
+) -> Vec<(String, String)> {
+    let existing_bindings: HashMap<&String, &String> =
+        ghostty_shortcuts.iter().map(|(key, value)| (value, key)).collect();
+
+    converted_gnome_shortcuts
+        .iter()
+        .filter(|(action, binding)| {
+            let conflicts =
+                ghostty_shortcuts.contains_key(*action) || existing_bindings.contains_key(binding);
+            !matches!(on_conflict, OnConflict::Skip) || !conflicts
+        })
+        .map(|(action, binding)| (action.clone(), binding.clone()))
+        .collect()
+}
+
+/// Finds the ghostty config line that currently defines `conflicting_action`'s effective binding,
+/// so `--on-conflict replace` can rewrite that line in place instead of appending a duplicate.
+///
+/// Only searches `origin_file` -- the file `GhosttyBindingHistory` already determined holds the
+/// binding actually in effect for `conflicting_action` -- rather than re-scanning every
+/// BFS-discovered file in discovery order, which could rewrite a shadowed definition in an
+/// earlier file while the effective one in a later `config-file` include is left untouched. If
+/// `conflicting_action` is redefined more than once within `origin_file`, the last such line is
+/// used, matching the same shadowing rule.
+///
+/// Args:
+/// - origin_file: the config file the action's effective binding comes from, from
+///     `GhosttyBindingHistory::origin_file`.
+/// - conflicting_action: the action whose effective keybind line should be located.
+fn find_conflicting_keybind_line(origin_file: &str, conflicting_action: &str) -> Option<(PathBuf, usize)> {
+    let ghostty_config_dir = config_dir().unwrap().join("ghostty");
+    let config_file = ghostty_config_dir.join(origin_file);
+
+    let line_idx = read_lines(&config_file)
+        .ok()?
+        .map_while(Result::ok)
+        .enumerate()
+        .filter_map(|(line_idx, line)| {
+            let (_, line_action) = parse_keybind_line(&line)?;
+            (line_action == conflicting_action).then_some(line_idx)
+        })
+        .last()?;
+
+    Some((config_file, line_idx))
+}
+
+/// Rewrites a single `keybind` line of an existing ghostty config file in place, used by
+/// `--on-conflict replace` to overwrite a colliding binding rather than appending a duplicate.
+fn replace_keybind_line(file: &Path, line_idx: usize, binding: &str, action: &str) {
+    let contents = std::fs::read_to_string(file).expect("File Error");
+    let mut lines: Vec<&str> = contents.lines().collect();
+    let new_line = format!("keybind = {binding}={action}");
+    lines[line_idx] = &new_line;
+    std::fs::write(file, lines.join("\n") + "\n").expect("File Error");
+}
+
+/// Updates the ghostty config with converted source shortcuts. A converted shortcut that
+/// collides with an existing binding is dropped under `skip`, rewritten in place over the
+/// colliding `keybind` line under `replace`, and appended anyway under `keep-both`.
+///
+/// Args:
+/// - converted_gnome_shortcuts: map from the action to the keybinding of the converted source
+///     shortcuts
+/// - ghostty_shortcuts: map from the action to the keybinding for the ghostty config shortcuts.
+///    This accounts for different config files and the order in which bindings are stated.
+/// - ghostty_origins: map from the action to its full binding history, used to find which file
+///     a colliding action's effective binding actually lives in, from
+///     `get_ghostty_shortcuts_with_origin`.
+/// - shortcuts_file_name: the source-specific config-file to write converted shortcuts into, from
+///     `SourceTerminal::shortcuts_file_name`.
+/// - on_conflict: how to handle a converted shortcut that collides with an existing one
 ///
 fn update_ghostty_config(
     converted_gnome_shortcuts: HashMap<String, String>,
     ghostty_shortcuts: HashMap<String, String>,
-    avoid_conflict: bool,
+    ghostty_origins: &HashMap<String, GhosttyBindingHistory>,
+    shortcuts_file_name: &str,
+    on_conflict: OnConflict,
 ) {
-    let re = Regex::new(r#"config-file\s*=\s*gnome-shortcuts"#).unwrap();
+    let re = Regex::new(&format!(r#"config-file\s*=\s*{}"#, regex::escape(shortcuts_file_name)))
+        .unwrap();
     let ghostty_config_dir = config_dir().unwrap().join("ghostty");
     let ghostty_config = ghostty_config_dir.join("config");
 
@@ -234,35 +689,132 @@ fn update_ghostty_config(
         .filter_map(Result::ok)
         .any(|line| re.is_match(&line));
 
-    let gnome_shortcuts_path = ghostty_config_dir.join("gnome-shortcuts");
+    let shortcuts_path = ghostty_config_dir.join(shortcuts_file_name);
 
-    let mut gnome_shortcuts_config: File = if !config_found {
+    let mut shortcuts_config: File = if !config_found {
         config_file.write_all(b"\n# Added by ghosttify\n").unwrap();
         config_file
-            .write_all(b"config-file=gnome-shortcuts\n")
+            .write_all(format!("config-file={shortcuts_file_name}\n").as_bytes())
             .unwrap();
-        File::create(gnome_shortcuts_path).expect("File Error")
+        File::create(shortcuts_path).expect("File Error")
     } else {
         OpenOptions::new()
             .append(true)
             .read(true)
-            .open(gnome_shortcuts_path)
+            .open(shortcuts_path)
             .expect("File Error")
     };
 
-    let keybindings: HashMap<&String, &String> = if avoid_conflict {
-        ghostty_shortcuts.iter().map(|(key, value)| (value, key) ).collect()
-    } else {
-        HashMap::new()
-    }; 
-
-    for (action, binding) in &converted_gnome_shortcuts {
-        if (avoid_conflict && (!ghostty_shortcuts.contains_key(action) && !keybindings.contains_key(binding))) || !avoid_conflict {
-            gnome_shortcuts_config
-                .write_all(format!("keybind = {}={}\n", binding, action).as_bytes())
-                .unwrap();
+    let planned = plan_ghostty_updates(&converted_gnome_shortcuts, &ghostty_shortcuts, &on_conflict);
+
+    for (action, binding) in &planned {
+        let conflicting_action = if ghostty_shortcuts.contains_key(action) {
+            Some(action.as_str())
+        } else {
+            ghostty_shortcuts
+                .iter()
+                .find(|(_, existing)| *existing == binding)
+                .map(|(other_action, _)| other_action.as_str())
+        };
+
+        if on_conflict == OnConflict::Replace {
+            if let Some(conflicting_action) = conflicting_action {
+                let origin_file = ghostty_origins
+                    .get(conflicting_action)
+                    .expect("conflicting action is present in ghostty_shortcuts, built from ghostty_origins")
+                    .origin_file();
+
+                if let Some((file, line_idx)) =
+                    find_conflicting_keybind_line(origin_file, conflicting_action)
+                {
+                    replace_keybind_line(&file, line_idx, binding, action);
+                    continue;
+                }
+            }
+        }
+
+        shortcuts_config
+            .write_all(format!("keybind = {}={}\n", binding, action).as_bytes())
+            .unwrap();
+    }
+}
+
+/// Removes the `# Added by ghosttify` block and every source's `config-file=<source>-shortcuts`
+/// include from the main ghostty config, and deletes any generated `<source>-shortcuts` file,
+/// cleanly rolling back a previous `--apply` regardless of which `--from` it used.
+fn undo_ghostty_config() {
+    undo_ghostty_config_in(&config_dir().unwrap().join("ghostty"));
+}
+
+/// Does the actual work of `undo_ghostty_config` against `ghostty_config_dir`, split out so a
+/// test can point it at a temporary directory instead of the real `~/.config/ghostty`.
+fn undo_ghostty_config_in(ghostty_config_dir: &Path) {
+    let ghostty_config = ghostty_config_dir.join("config");
+    let shortcuts_file_names = [
+        Gnome.shortcuts_file_name(),
+        Kitty.shortcuts_file_name(),
+        Alacritty.shortcuts_file_name(),
+    ];
+
+    if let Ok(contents) = std::fs::read_to_string(&ghostty_config) {
+        let cleaned = contents
+            .lines()
+            .filter(|line| {
+                let line = line.trim();
+                line != "# Added by ghosttify"
+                    && !shortcuts_file_names
+                        .iter()
+                        .any(|name| line == format!("config-file={name}"))
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        std::fs::write(&ghostty_config, cleaned + "\n").expect("File Error");
+    }
+
+    for name in shortcuts_file_names {
+        let _ = std::fs::remove_file(ghostty_config_dir.join(name));
+    }
+}
+
+/// Prints a colored diff of what `--apply` would write to `shortcuts_file_name` without writing
+/// anything. Lines already present in the file are printed dim and unchanged; lines that would
+/// be newly added are printed green with a `+` prefix, mirroring rustfmt's `--check` diff style.
+///
+/// Args:
+/// - converted_gnome_shortcuts: map from the action to the keybinding of the converted source
+///     shortcuts
+/// - ghostty_shortcuts: map from the action to the keybinding for the ghostty config shortcuts
+/// - on_conflict: how to handle a converted shortcut that collides with an existing one
+/// - shortcuts_file_name: the source-specific config-file `--apply` would write into, from
+///     `SourceTerminal::shortcuts_file_name`.
+fn print_dry_run_diff(
+    converted_gnome_shortcuts: &HashMap<String, String>,
+    ghostty_shortcuts: &HashMap<String, String>,
+    on_conflict: &OnConflict,
+    shortcuts_file_name: &str,
+) {
+    let ghostty_config_dir = config_dir().unwrap().join("ghostty");
+    let shortcuts_path = ghostty_config_dir.join(shortcuts_file_name);
+
+    println!("{}", format!("--- {shortcuts_file_name}").bold());
+    if let Ok(lines) = read_lines(&shortcuts_path) {
+        for line in lines.map_while(Result::ok) {
+            println!(" {}", line.dimmed());
+        }
+    }
+
+    let planned = plan_ghostty_updates(converted_gnome_shortcuts, ghostty_shortcuts, on_conflict);
+    for (action, binding) in &planned {
+        let conflicts = ghostty_shortcuts.contains_key(action)
+            || ghostty_shortcuts.values().any(|existing| existing == binding);
+
+        if *on_conflict == OnConflict::Replace && conflicts {
+            println!("{}", format!("~keybind = {}={}", binding, action).yellow());
+        } else {
+            println!("{}", format!("+keybind = {}={}", binding, action).green());
         }
     }
+    println!();
 }
 
 fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
@@ -276,21 +828,263 @@ where
 fn main() {
     let args = Cli::parse();
 
-    let gnome_shortcuts = get_gnome_shortcuts();
-    let converted_shortcuts = convert_gnome_to_ghostty_shortcuts(gnome_shortcuts);
-    if args.gnome {
-        println!("{}", "Gnome Shortcuts".italic().bold().bright_blue());
+    if args.undo {
+        undo_ghostty_config();
+        return;
+    }
+
+    let source = source_terminal(&args.from);
+
+    if args.dump_map {
+        println!("{}", embedded_map_string(source.map_file_name()));
+        return;
+    }
+
+    let source_to_ghostty = load_map(source.map_file_name(), args.map.as_deref());
+
+    let source_shortcuts = source.read_shortcuts();
+    let converted_shortcuts = convert_shortcuts_to_ghostty(
+        source_shortcuts,
+        &source_to_ghostty,
+        source.uses_leader_sequences(),
+    );
+    if args.converted {
+        println!("{}", format!("{:?} Shortcuts", args.from).italic().bold().bright_blue());
         print_ghostty_shortcuts(&converted_shortcuts);
     }
 
-    let ghostty_shortcuts = get_ghostty_shortcuts();
+    let ghostty_origins = get_ghostty_shortcuts_with_origin();
+    let ghostty_shortcuts = flatten_ghostty_shortcuts(&ghostty_origins);
     if args.ghostty {
         println!("{}", "Ghostty Shortcuts".italic().bold().bright_blue());
-        print_ghostty_shortcuts(&ghostty_shortcuts);
+        if args.show_origin {
+            print_ghostty_shortcuts_with_origin(&ghostty_origins);
+        } else {
+            print_ghostty_shortcuts(&ghostty_shortcuts);
+        }
+    }
+
+    if args.dry_run {
+        print_dry_run_diff(
+            &converted_shortcuts,
+            &ghostty_shortcuts,
+            &args.on_conflict,
+            source.shortcuts_file_name(),
+        );
+    } else if args.apply {
+        update_ghostty_config(
+            converted_shortcuts,
+            ghostty_shortcuts,
+            &ghostty_origins,
+            source.shortcuts_file_name(),
+            args.on_conflict,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alacritty_keys_map() -> HashMap<String, String> {
+        load_map("alacritty_to_ghostty.json", None)["keys"].clone()
+    }
+
+    fn kitty_keys_map() -> HashMap<String, String> {
+        load_map("kitty_to_ghostty.json", None)["keys"].clone()
+    }
+
+    #[test]
+    fn convert_shortcut_to_ghostty_flattens_gnome_brackets() {
+        let keys = HashMap::from([
+            ("Primary".to_string(), "ctrl".to_string()),
+            ("Shift".to_string(), "shift".to_string()),
+        ]);
+        let shortcut = "<Primary><Shift>T".to_string();
+        assert_eq!(
+            convert_shortcut_to_ghostty(&shortcut, &keys, false),
+            Some("ctrl+shift+T".to_string())
+        );
+    }
+
+    #[test]
+    fn convert_shortcut_to_ghostty_maps_plus_joined_alacritty_mods() {
+        let keys = alacritty_keys_map();
+        let shortcut = "Control+Shift+T".to_string();
+        assert_eq!(
+            convert_shortcut_to_ghostty(&shortcut, &keys, false),
+            Some("ctrl+shift+T".to_string())
+        );
+    }
+
+    #[test]
+    fn convert_shortcut_to_ghostty_preserves_kitty_leader_sequence() {
+        let keys = kitty_keys_map();
+        let shortcut = "ctrl+a>c".to_string();
+        assert_eq!(
+            convert_shortcut_to_ghostty(&shortcut, &keys, true),
+            Some("ctrl+a>c".to_string())
+        );
+    }
+
+    #[test]
+    fn convert_shortcut_to_ghostty_drops_disabled_shortcut() {
+        let keys = HashMap::from([("a".to_string(), "disabled".to_string())]);
+        let shortcut = "a".to_string();
+        assert_eq!(convert_shortcut_to_ghostty(&shortcut, &keys, false), None);
+    }
+
+    #[test]
+    fn parse_alacritty_shortcuts_splits_pipe_joined_multi_modifier_mods() {
+        let conf = r#"
+            [[keyboard.bindings]]
+            key = "T"
+            mods = "Control|Shift"
+            action = "SpawnNewInstance"
+        "#;
+        let shortcuts = parse_alacritty_shortcuts(conf);
+        assert_eq!(
+            shortcuts.get("SpawnNewInstance"),
+            Some(&"Control+Shift+T".to_string())
+        );
     }
 
-    if args.apply {
-        update_ghostty_config(converted_shortcuts, ghostty_shortcuts, args.avoid_conflict);
-       
+    #[test]
+    fn parse_alacritty_shortcuts_handles_missing_mods() {
+        let conf = r#"
+            [[keyboard.bindings]]
+            key = "Escape"
+            action = "ToggleFullscreen"
+        "#;
+        let shortcuts = parse_alacritty_shortcuts(conf);
+        assert_eq!(
+            shortcuts.get("ToggleFullscreen"),
+            Some(&"Escape".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_kitty_shortcuts_reads_map_lines() {
+        let lines = vec![
+            "map ctrl+shift+t new_tab".to_string(),
+            "# a comment, not a binding".to_string(),
+            "map ctrl+a>c new_tab_after_current".to_string(),
+        ];
+        let shortcuts = parse_kitty_shortcuts(lines);
+        assert_eq!(shortcuts.get("new_tab"), Some(&"ctrl+shift+t".to_string()));
+        assert_eq!(
+            shortcuts.get("new_tab_after_current"),
+            Some(&"ctrl+a>c".to_string())
+        );
+    }
+
+    #[test]
+    fn end_to_end_converts_multi_modifier_alacritty_binding_to_valid_ghostty_combo() {
+        let conf = r#"
+            [[keyboard.bindings]]
+            key = "T"
+            mods = "Control|Shift"
+            action = "SpawnNewInstance"
+        "#;
+        let source_shortcuts = parse_alacritty_shortcuts(conf);
+        let source_to_ghostty = load_map("alacritty_to_ghostty.json", None);
+        let converted = convert_shortcuts_to_ghostty(source_shortcuts, &source_to_ghostty, false);
+        assert_eq!(converted.get("new_window"), Some(&"ctrl+shift+T".to_string()));
+    }
+
+    #[test]
+    fn parse_keybind_line_preserves_prefix() {
+        for prefix in ["global:", "all:", "unconsumed:"] {
+            let line = format!("keybind = {prefix}ctrl+q=quit");
+            assert_eq!(
+                parse_keybind_line(&line),
+                Some((format!("{prefix}ctrl+q"), "quit".to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn parse_keybind_line_preserves_leader_chain() {
+        assert_eq!(
+            parse_keybind_line("keybind = ctrl+a>c=new_tab"),
+            Some(("ctrl+a>c".to_string(), "new_tab".to_string()))
+        );
+    }
+
+    #[test]
+    fn parse_keybind_line_keeps_action_argument_whole() {
+        assert_eq!(
+            parse_keybind_line("keybind = ctrl+shift+2=goto_tab:2"),
+            Some(("ctrl+shift+2".to_string(), "goto_tab:2".to_string()))
+        );
+    }
+
+    #[test]
+    fn parse_keybind_line_only_splits_on_first_equals() {
+        assert_eq!(
+            parse_keybind_line("keybind = ctrl+e=text:foo=bar"),
+            Some(("ctrl+e".to_string(), "text:foo=bar".to_string()))
+        );
+    }
+
+    #[test]
+    fn parse_keybind_line_rejects_non_keybind_lines() {
+        assert_eq!(parse_keybind_line("font-size = 12"), None);
+    }
+
+    /// A fresh, empty directory under the OS temp dir, unique to this test process and call site,
+    /// so `replace_keybind_line`/`undo_ghostty_config_in` tests can operate on real files without
+    /// touching the user's actual ghostty config or colliding with other tests run in parallel.
+    fn unique_temp_dir(name: &str) -> PathBuf {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let id = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let dir = std::env::temp_dir().join(format!("ghosttify_test_{name}_{}_{id}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn replace_keybind_line_rewrites_only_the_targeted_line() {
+        let dir = unique_temp_dir("replace_keybind_line");
+        let config = dir.join("config");
+        std::fs::write(&config, "keybind = ctrl+t=new_tab\nkeybind = ctrl+w=close_surface\n").unwrap();
+
+        replace_keybind_line(&config, 0, "ctrl+n", "new_tab");
+
+        let contents = std::fs::read_to_string(&config).unwrap();
+        assert_eq!(
+            contents,
+            "keybind = ctrl+n=new_tab\nkeybind = ctrl+w=close_surface\n"
+        );
+    }
+
+    #[test]
+    fn undo_ghostty_config_in_removes_ghosttify_block_and_shortcuts_files() {
+        let dir = unique_temp_dir("undo_ghostty_config_in_removes");
+        std::fs::write(
+            dir.join("config"),
+            "font-size = 12\n\n# Added by ghosttify\nconfig-file=kitty-shortcuts\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("kitty-shortcuts"), "keybind = ctrl+t=new_tab\n").unwrap();
+
+        undo_ghostty_config_in(&dir);
+
+        let contents = std::fs::read_to_string(dir.join("config")).unwrap();
+        assert_eq!(contents, "font-size = 12\n\n");
+        assert!(!dir.join("kitty-shortcuts").exists());
+    }
+
+    #[test]
+    fn undo_ghostty_config_in_is_a_no_op_without_a_prior_apply() {
+        let dir = unique_temp_dir("undo_ghostty_config_in_noop");
+        std::fs::write(dir.join("config"), "font-size = 12\n").unwrap();
+
+        undo_ghostty_config_in(&dir);
+
+        let contents = std::fs::read_to_string(dir.join("config")).unwrap();
+        assert_eq!(contents, "font-size = 12\n");
     }
 }